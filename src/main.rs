@@ -1,10 +1,93 @@
 #[derive(Clone, Copy, Debug)]
 struct V2 (f32, f32);
 
+impl V2 {
+    fn lerp(self, other: V2, t: f32) -> V2 {
+        V2(self.0 + (other.0 - self.0)*t, self.1 + (other.1 - self.1)*t)
+    }
+
+    // perpendicular distance from `self` to the (infinite) line through `a` and `b`.
+    fn distance_to_line(self, a: V2, b: V2) -> f32 {
+        let d = V2(b.0 - a.0, b.1 - a.1);
+        let len = (d.0*d.0 + d.1*d.1).sqrt();
+        if len == 0.0 {
+            let dx = self.0 - a.0;
+            let dy = self.1 - a.1;
+            return (dx*dx + dy*dy).sqrt();
+        }
+        ((self.0 - a.0)*d.1 - (self.1 - a.1)*d.0).abs() / len
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 struct Line (V2, V2);
 
 
+// a single curve primitive; a path is a sequence of these.
+#[derive(Clone, Copy, Debug)]
+enum Segment {
+    Line(V2, V2),
+    Quad(V2, V2, V2),
+    Cubic(V2, V2, V2, V2),
+}
+
+// how closely a flattened polyline must hug a curve, in the same units as the path's points.
+const FLATTEN_TOLERANCE: f32 = 0.1;
+
+impl Segment {
+    // recursive de Casteljau subdivision: split at t=0.5 and stop once the
+    // control points are within `tolerance` of the chord from start to end.
+    fn flatten(&self, tolerance: f32) -> Vec<Line> {
+        match *self {
+            Segment::Line(a, b) => vec![Line(a, b)],
+
+            Segment::Quad(a, c, b) => {
+                if c.distance_to_line(a, b) <= tolerance {
+                    vec![Line(a, b)]
+                }
+                else {
+                    let ac = a.lerp(c, 0.5);
+                    let cb = c.lerp(b, 0.5);
+                    let m  = ac.lerp(cb, 0.5);
+
+                    let mut lines = Segment::Quad(a, ac, m).flatten(tolerance);
+                    lines.extend(Segment::Quad(m, cb, b).flatten(tolerance));
+                    lines
+                }
+            }
+
+            Segment::Cubic(a, c0, c1, b) => {
+                let d0 = c0.distance_to_line(a, b);
+                let d1 = c1.distance_to_line(a, b);
+                if d0.max(d1) <= tolerance {
+                    vec![Line(a, b)]
+                }
+                else {
+                    let ac0    = a.lerp(c0, 0.5);
+                    let c0c1   = c0.lerp(c1, 0.5);
+                    let c1b    = c1.lerp(b, 0.5);
+                    let ac0_m  = ac0.lerp(c0c1, 0.5);
+                    let c1b_m  = c0c1.lerp(c1b, 0.5);
+                    let m      = ac0_m.lerp(c1b_m, 0.5);
+
+                    let mut lines = Segment::Cubic(a, ac0, ac0_m, m).flatten(tolerance);
+                    lines.extend(Segment::Cubic(m, c1b_m, c1b, b).flatten(tolerance));
+                    lines
+                }
+            }
+        }
+    }
+}
+
+fn flatten_path(path: &Vec<Segment>, tolerance: f32) -> Vec<Line> {
+    let mut lines = vec![];
+    for segment in path {
+        lines.extend(segment.flatten(tolerance));
+    }
+    lines
+}
+
+
 impl Line {
     fn direction(self) -> V2 {
         V2(self.1.0 - self.0.0, self.1.1 - self.0.1)
@@ -52,68 +135,183 @@ impl Line {
         Some((t, u))
     }
 
-    fn make_thicc(self, width: f32) -> Vec<Line> {
+    // unit left-hand normal of this line's direction.
+    fn normal(self) -> V2 {
         let direction = self.direction();
-        let left = V2(-direction.1, direction.0);
+        let length = (direction.0*direction.0 + direction.1*direction.1).sqrt();
+        V2(-direction.1/length, direction.0/length)
+    }
 
-        let length = (left.0*left.0 + left.1*left.1).sqrt();
-        let normal = V2(left.0/length, left.1/length);
+    // this line, shifted sideways along its normal by `amount`.
+    fn offset(self, amount: f32) -> Line {
+        let n = self.normal();
+        Line(
+            V2(self.0.0 + amount*n.0, self.0.1 + amount*n.1),
+            V2(self.1.0 + amount*n.0, self.1.1 + amount*n.1),
+        )
+    }
+
+    // coverage of a stroke of `half_width` around this segment at point `p`,
+    // via its signed distance field: `width/2 - distance + 0.5`, clamped.
+    fn coverage_at(self, p: V2, half_width: f32) -> f32 {
+        let ab = self.direction();
+        let len_sq = ab.0*ab.0 + ab.1*ab.1;
+
+        let h = if len_sq > 0.0 {
+            (((p.0 - self.0.0)*ab.0 + (p.1 - self.0.1)*ab.1) / len_sq).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        let c = V2(self.0.0 + h*ab.0, self.0.1 + h*ab.1);
+        let d = ((p.0 - c.0).powi(2) + (p.1 - c.1).powi(2)).sqrt();
+        (half_width - d + 0.5).clamp(0.0, 1.0)
+    }
+}
 
-        let width = width/2.0;
 
-        let p0 = V2(self.0.0 + width*normal.0, self.0.1 + width*normal.1);
-        let p1 = V2(self.1.0 + width*normal.0, self.1.1 + width*normal.1);
-        let p2 = V2(self.1.0 - width*normal.0, self.1.1 - width*normal.1);
-        let p3 = V2(self.0.0 - width*normal.0, self.0.1 - width*normal.1);
-        vec![
-            Line(p0, p1),
-            Line(p1, p2),
-            Line(p2, p3),
-            Line(p3, p0),
-        ]
+enum FillRule {
+    NonZero,
+    EvenOdd,
+}
+
+impl FillRule {
+    fn is_filled(&self, winding: i32) -> bool {
+        match self {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
     }
 }
 
+// an edge crossing a scanline: the x-coordinate it crosses at, and the
+// signed winding delta it contributes (+1 going up, -1 going down).
+struct Crossing { x: f32, delta: i32 }
 
-fn compute_winding(path: &Vec<Line>, x: f32, y: f32) -> i32 {
-    // shoot a ray towards negative infinity in x.
-    let ray = Line(V2(x, y), V2(x - 1.0, y));
+// all edges crossing the horizontal line `y`, sorted left to right.
+fn scan_crossings(path: &Vec<Line>, y: f32) -> Vec<Crossing> {
+    let mut crossings = vec![];
 
-    let mut winding = 0;
     for line in path {
-        // do the lines intersect?
-        if let Some((t, u)) = ray.intersect(*line) {
-
-            // do the ray/segment actually intersect?
-            let ray_hit     = t >= 0.0;
-            let segment_hit = u >= 0.0 && u <= 1.0;
-            if ray_hit && segment_hit {
-                // line goes up -> positive winding, else negative.
-                let delta = if line.1.1 >= line.0.1 { 1 } else { -1 };
-                winding += delta;
+        let y0 = line.0.1;
+        let y1 = line.1.1;
+        if y0 == y1 {
+            continue // horizontal edges never cross a scanline.
+        }
+
+        let lo = y0.min(y1);
+        let hi = y0.max(y1);
+        if y < lo || y >= hi {
+            continue // edge's y-range doesn't contain this scanline.
+        }
+
+        let t = (y - y0) / (y1 - y0);
+        let x = line.0.0 + t*(line.1.0 - line.0.0);
+        let delta = if y1 > y0 { 1 } else { -1 };
+        crossings.push(Crossing { x, delta });
+    }
+
+    crossings.sort_by(|a, b| a.x.partial_cmp(&b.x).unwrap());
+    crossings
+}
+
+// sweep `crossings` left to right, calling `emit(x0, x1)` for every span
+// whose accumulated winding number is filled under `fill_rule`.
+fn sweep_spans(crossings: &[Crossing], fill_rule: &FillRule, mut emit: impl FnMut(f32, f32)) {
+    let mut winding = 0;
+    for i in 0..crossings.len() {
+        winding += crossings[i].delta;
+        if fill_rule.is_filled(winding) {
+            if let Some(next) = crossings.get(i + 1) {
+                emit(crossings[i].x, next.x);
             }
         }
     }
+}
 
-    winding
+fn rasterize(path: &Vec<Line>, w: u32, h: u32, fill_rule: FillRule) {
+    let mut row = vec![false; w as usize];
+
+    for y in 0..h {
+        let crossings = scan_crossings(path, y as f32 + 0.5);
+
+        for cell in row.iter_mut() {
+            *cell = false;
+        }
+        sweep_spans(&crossings, &fill_rule, |x0, x1| {
+            // fill every pixel whose center (x+0.5) falls in [x0, x1).
+            let first = (x0 - 0.5).ceil().max(0.0) as usize;
+            let last  = ((x1 - 0.5).ceil() as i64).clamp(0, w as i64) as usize;
+            if first < last {
+                for cell in &mut row[first.min(w as usize)..last] {
+                    *cell = true;
+                }
+            }
+        });
+
+        for filled in &row {
+            print!("{}", if *filled { "#" } else { "." });
+        }
+        println!();
+    }
+    println!()
 }
 
 
-enum FillRule {
-    NonZero,
-    EvenOdd,
+// sub-scanlines sampled per pixel row when computing analytic coverage.
+const COVERAGE_SAMPLES: u32 = 4;
+
+// accumulate the fractional horizontal overlap of the span [x0, x1) with
+// each pixel's [x, x+1) extent into `row`.
+fn accumulate_span(row: &mut [f32], x0: f32, x1: f32, w: u32) {
+    let x0 = x0.max(0.0);
+    let x1 = x1.min(w as f32);
+    if x1 <= x0 {
+        return
+    }
+
+    let first = x0.floor() as usize;
+    let last  = x1.ceil() as usize;
+    for (i, cell) in row[first..last.min(w as usize)].iter_mut().enumerate() {
+        let px = (first + i) as f32;
+        let lo = px.max(x0);
+        let hi = (px + 1.0).min(x1);
+        *cell += (hi - lo).max(0.0);
+    }
 }
 
-fn rasterize(path: &Vec<Line>, w: u32, h: u32, fill_rule: FillRule) {
+// renders `path` to a `w*h` buffer of per-pixel coverage in [0, 1], by
+// supersampling `COVERAGE_SAMPLES` scanlines per pixel row and averaging
+// each sample's fractional span overlap.
+fn rasterize_coverage(path: &Vec<Line>, w: u32, h: u32, fill_rule: FillRule) -> Vec<f32> {
+    let mut coverage = vec![0.0; (w*h) as usize];
+
     for y in 0..h {
-        for x in 0..w {
-            let winding = compute_winding(path, x as f32 + 0.5, y as f32 + 0.5);
+        let row = &mut coverage[(y*w) as usize .. (y*w + w) as usize];
+
+        for sample in 0..COVERAGE_SAMPLES {
+            let sy = y as f32 + (sample as f32 + 0.5) / COVERAGE_SAMPLES as f32;
+            let crossings = scan_crossings(path, sy);
+            sweep_spans(&crossings, &fill_rule, |x0, x1| accumulate_span(row, x0, x1, w));
+        }
 
-            let filled = match fill_rule {
-                FillRule::NonZero => winding != 0,
-                FillRule::EvenOdd => winding % 2 != 0,
-            };
-            print!("{}", if filled { "#" } else { "." });
+        for cell in row {
+            *cell = (*cell / COVERAGE_SAMPLES as f32).clamp(0.0, 1.0);
+        }
+    }
+
+    coverage
+}
+
+// ASCII shades from empty to fully covered, for printing a coverage buffer.
+const COVERAGE_SHADES: &[u8] = b" .:-=+*#%@";
+
+fn print_coverage(coverage: &[f32], w: u32, h: u32) {
+    for y in 0..h {
+        for x in 0..w {
+            let c = coverage[(y*w + x) as usize];
+            let index = (c * (COVERAGE_SHADES.len() - 1) as f32).round() as usize;
+            print!("{}", COVERAGE_SHADES[index] as char);
         }
         println!();
     }
@@ -121,59 +319,392 @@ fn rasterize(path: &Vec<Line>, w: u32, h: u32, fill_rule: FillRule) {
 }
 
 
-fn fill(path: &Vec<Line>, w: u32, h: u32, fill_rule: FillRule) {
-    rasterize(path, w, h, fill_rule);
+// splits a flat line list into maximal runs that connect end-to-start,
+// i.e. the closed contours that make it up.
+fn split_contours(path: &Vec<Line>) -> Vec<Vec<Line>> {
+    let mut contours = vec![];
+    let mut current: Vec<Line> = vec![];
+
+    for &line in path {
+        if let Some(prev) = current.last() {
+            let connects = (prev.1.0 - line.0.0).abs() < 1e-4 && (prev.1.1 - line.0.1).abs() < 1e-4;
+            if !connects {
+                contours.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(line);
+    }
+    if !current.is_empty() {
+        contours.push(current);
+    }
+
+    contours
 }
 
-fn stroke(path: &Vec<Line>, w: u32, h: u32, stroke_width: f32) {
-    let mut stroke = vec![];
-    for line in path {
-        stroke.extend(line.make_thicc(stroke_width));
+// Sutherland-Hodgman: clips the (implicitly closed) `polygon` against the
+// half-plane where `inside` holds, crossing `edge` at the boundary.
+fn clip_edge(polygon: &[V2], edge: Line, inside: impl Fn(V2) -> bool) -> Vec<V2> {
+    let mut output = vec![];
+
+    for i in 0..polygon.len() {
+        let from = polygon[i];
+        let to = polygon[(i + 1) % polygon.len()];
+
+        let from_inside = inside(from);
+        let to_inside = inside(to);
+
+        if from_inside != to_inside {
+            if let Some((_, u)) = edge.intersect(Line(from, to)) {
+                let d = Line(from, to).direction();
+                output.push(V2(from.0 + u*d.0, from.1 + u*d.1));
+            }
+        }
+        if to_inside {
+            output.push(to);
+        }
+    }
+
+    output
+}
+
+// clips every closed contour in `path` against the `w`x`h` canvas rectangle,
+// running each through the left, top, right, and bottom edges in sequence.
+fn clip_to_rect(path: &Vec<Line>, w: u32, h: u32) -> Vec<Line> {
+    let (w, h) = (w as f32, h as f32);
+
+    let mut lines = vec![];
+    for contour in split_contours(path) {
+        let mut points: Vec<V2> = contour.iter().map(|line| line.0).collect();
+
+        points = clip_edge(&points, Line(V2(0.0, 0.0), V2(0.0, 1.0)), |p| p.0 >= 0.0);
+        points = clip_edge(&points, Line(V2(0.0, 0.0), V2(1.0, 0.0)), |p| p.1 >= 0.0);
+        points = clip_edge(&points, Line(V2(w, 0.0), V2(w, 1.0)), |p| p.0 <= w);
+        points = clip_edge(&points, Line(V2(0.0, h), V2(1.0, h)), |p| p.1 <= h);
+
+        if points.len() >= 2 {
+            lines.extend(polygon_to_lines(&points));
+        }
+    }
+
+    lines
+}
+
+fn fill(path: &Vec<Segment>, w: u32, h: u32, fill_rule: FillRule) {
+    let lines = flatten_path(path, FLATTEN_TOLERANCE);
+    let lines = clip_to_rect(&lines, w, h);
+    rasterize(&lines, w, h, fill_rule);
+}
+
+fn fill_coverage(path: &Vec<Segment>, w: u32, h: u32, fill_rule: FillRule) -> Vec<f32> {
+    let lines = flatten_path(path, FLATTEN_TOLERANCE);
+    let lines = clip_to_rect(&lines, w, h);
+    rasterize_coverage(&lines, w, h, fill_rule)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum JoinStyle { Miter, Bevel, Round }
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CapStyle { Butt, Square, Round }
+
+// segments used to approximate a round join/cap's arc.
+const ROUND_STEPS: u32 = 8;
+
+// miters longer than this multiple of the half-width fall back to a bevel.
+const DEFAULT_MITER_LIMIT: f32 = 4.0;
+
+// appends an arc of `steps` short segments around `center`, from `from` to
+// `to` (both assumed equidistant from `center`), sweeping the short way —
+// unless `outward` is given and disagrees with that sweep's direction, in
+// which case the arc goes the long way around instead. `outward` is needed
+// whenever `from`/`to` can be exactly opposite (180°) across `center`, where
+// "the short way" is an arbitrary, float-sign-dependent tie.
+fn append_arc(points: &mut Vec<V2>, center: V2, from: V2, to: V2, steps: u32, outward: Option<V2>) {
+    let radius = ((from.0 - center.0).powi(2) + (from.1 - center.1).powi(2)).sqrt();
+    let a0 = (from.1 - center.1).atan2(from.0 - center.0);
+    let a1 = (to.1 - center.1).atan2(to.0 - center.0);
+
+    let mut da = a1 - a0;
+    while da >  std::f32::consts::PI { da -= std::f32::consts::TAU; }
+    while da < -std::f32::consts::PI { da += std::f32::consts::TAU; }
+
+    if let Some(dir) = outward {
+        let mid = a0 + da*0.5;
+        let disagrees = mid.cos()*dir.0 + mid.sin()*dir.1 < 0.0;
+        if disagrees {
+            da -= da.signum()*std::f32::consts::TAU;
+        }
+    }
+
+    for i in 1..steps {
+        let a = a0 + da*(i as f32/steps as f32);
+        points.push(V2(center.0 + radius*a.cos(), center.1 + radius*a.sin()));
     }
-    fill(&stroke, w, h, FillRule::NonZero);
+    points.push(to);
+}
+
+// appends the join between two consecutive offset segments meeting at the
+// original path's `vertex`, bridging from `prev.1` to `next.0`.
+fn append_join(points: &mut Vec<V2>, vertex: V2, prev: Line, next: Line, join: JoinStyle, miter_limit: f32) {
+    points.push(prev.1);
+
+    match join {
+        JoinStyle::Bevel => {
+            points.push(next.0);
+        }
+
+        JoinStyle::Round => {
+            append_arc(points, vertex, prev.1, next.0, ROUND_STEPS, None);
+        }
+
+        JoinStyle::Miter => {
+            let half_width = ((prev.1.0 - vertex.0).powi(2) + (prev.1.1 - vertex.1).powi(2)).sqrt();
+            let miter = prev.intersect(next)
+                .map(|(t, _)| V2(prev.0.0 + t*prev.direction().0, prev.0.1 + t*prev.direction().1))
+                .filter(|m| {
+                    let len = ((m.0 - vertex.0).powi(2) + (m.1 - vertex.1).powi(2)).sqrt();
+                    half_width > 0.0 && len/half_width <= miter_limit
+                });
+
+            if let Some(miter) = miter {
+                points.push(miter);
+            }
+            points.push(next.0);
+        }
+    }
+}
+
+// appends the cap at a path endpoint, bridging from `points.last()` (== `p_near`)
+// to `p_far`, the offset points on either side of the path's terminal `vertex`.
+// `dir` is the unit tangent pointing outward, away from the path.
+fn append_cap(points: &mut Vec<V2>, vertex: V2, dir: V2, p_near: V2, p_far: V2, half_width: f32, cap: CapStyle) {
+    match cap {
+        CapStyle::Butt => {
+            points.push(p_far);
+        }
+
+        CapStyle::Square => {
+            points.push(V2(p_near.0 + dir.0*half_width, p_near.1 + dir.1*half_width));
+            points.push(V2(p_far.0  + dir.0*half_width, p_far.1  + dir.1*half_width));
+            points.push(p_far);
+        }
+
+        CapStyle::Round => {
+            append_arc(points, vertex, p_near, p_far, ROUND_STEPS, Some(dir));
+        }
+    }
+}
+
+fn polygon_to_lines(points: &[V2]) -> Vec<Line> {
+    let n = points.len();
+    (0..n).map(|i| Line(points[i], points[(i + 1) % n])).collect()
+}
+
+// offsets `path` sideways by `amount` (signed, along each segment's normal),
+// joining consecutive segments per `join`. `closed` wraps the join back
+// around to the first segment instead of leaving the ends loose.
+fn offset_side(path: &[Line], amount: f32, join: JoinStyle, miter_limit: f32, closed: bool) -> Vec<V2> {
+    let offsets: Vec<Line> = path.iter().map(|line| line.offset(amount)).collect();
+
+    let mut points = vec![offsets[0].0];
+    for i in 0..offsets.len() - 1 {
+        append_join(&mut points, path[i].1, offsets[i], offsets[i + 1], join, miter_limit);
+    }
+
+    if closed {
+        append_join(&mut points, path[path.len() - 1].1, *offsets.last().unwrap(), offsets[0], join, miter_limit);
+    }
+    else {
+        points.push(offsets.last().unwrap().1);
+    }
+
+    points
+}
+
+// builds a single stroke outline for a connected path (consecutive lines
+// sharing endpoints), by offsetting each segment by +-width/2 along its
+// normal and joining/capping per `join`/`cap`; miters longer than
+// `miter_limit` (as a multiple of the half-width) fall back to a bevel.
+// feed the result to `fill` with `FillRule::NonZero`.
+fn stroke_outline(path: &[Line], width: f32, join: JoinStyle, cap: CapStyle, miter_limit: f32) -> Vec<Line> {
+    if path.is_empty() {
+        return vec![]
+    }
+
+    let half_width = width/2.0;
+    let closed = {
+        let start = path[0].0;
+        let end = path[path.len() - 1].1;
+        (start.0 - end.0).abs() < 1e-4 && (start.1 - end.1).abs() < 1e-4
+    };
+
+    let left  = offset_side(path, half_width, join, miter_limit, closed);
+    let right = offset_side(path, -half_width, join, miter_limit, closed);
+
+    if closed {
+        // the two rings run the same way; reverse one so the enclosed
+        // interior cancels to a winding of zero.
+        let right: Vec<V2> = right.iter().rev().copied().collect();
+        let mut lines = polygon_to_lines(&left);
+        lines.extend(polygon_to_lines(&right));
+        return lines
+    }
+
+    let mut points = left.clone();
+
+    let end_dir = {
+        let d = path[path.len() - 1].direction();
+        let len = (d.0*d.0 + d.1*d.1).sqrt();
+        V2(d.0/len, d.1/len)
+    };
+    append_cap(&mut points, path[path.len() - 1].1, end_dir, *left.last().unwrap(), *right.last().unwrap(), half_width, cap);
+
+    points.extend(right.iter().rev().skip(1));
+
+    let start_dir = {
+        let d = path[0].direction();
+        let len = (d.0*d.0 + d.1*d.1).sqrt();
+        V2(-d.0/len, -d.1/len)
+    };
+    append_cap(&mut points, path[0].0, start_dir, right[0], left[0], half_width, cap);
+
+    points.pop(); // closes back onto points[0] already; drop the duplicate.
+    polygon_to_lines(&points)
+}
+
+// `path` is a list of independent subpaths (contours); each is stroked and
+// joined on its own, and all of their outlines are filled together.
+fn stroke(path: &Vec<Vec<Segment>>, w: u32, h: u32, stroke_width: f32) {
+    stroke_styled(path, w, h, stroke_width, JoinStyle::Miter, CapStyle::Butt, DEFAULT_MITER_LIMIT);
+}
+
+fn stroke_styled(path: &Vec<Vec<Segment>>, w: u32, h: u32, stroke_width: f32, join: JoinStyle, cap: CapStyle, miter_limit: f32) {
+    let mut outline = vec![];
+    for subpath in path {
+        let lines = flatten_path(subpath, FLATTEN_TOLERANCE);
+        outline.extend(stroke_outline(&lines, stroke_width, join, cap, miter_limit));
+    }
+
+    let outline = outline.into_iter().map(|l| Segment::Line(l.0, l.1)).collect();
+    fill(&outline, w, h, FillRule::NonZero);
+}
+
+// renders a `w*h` coverage buffer for `path`, stroked at `width` via the
+// segments' signed distance fields rather than a filled outline. handles
+// thin and sub-pixel widths cleanly; every segment ends and joins round,
+// since join and cap styles do not apply here.
+fn rasterize_sdf_stroke(path: &Vec<Line>, w: u32, h: u32, width: f32) -> Vec<f32> {
+    let half_width = width/2.0;
+    let mut coverage = vec![0.0; (w*h) as usize];
+
+    for y in 0..h {
+        for x in 0..w {
+            let p = V2(x as f32 + 0.5, y as f32 + 0.5);
+            let mut cov = 0.0;
+            for line in path {
+                cov = f32::max(cov, line.coverage_at(p, half_width));
+            }
+            coverage[(y*w + x) as usize] = cov;
+        }
+    }
+
+    coverage
+}
+
+fn stroke_sdf(path: &Vec<Vec<Segment>>, w: u32, h: u32, width: f32) -> Vec<f32> {
+    let mut lines = vec![];
+    for subpath in path {
+        lines.extend(flatten_path(subpath, FLATTEN_TOLERANCE));
+    }
+    rasterize_sdf_stroke(&lines, w, h, width)
 }
 
 
 fn main() {
     fill(&vec![
-        Line(V2( 2.0, 9.0), V2(15.0, 1.0)),
-        Line(V2(15.0, 1.0), V2(28.0, 9.0)),
-        Line(V2(28.0, 9.0), V2( 2.0, 9.0)),
-
-        Line(V2( 7.0, 8.0), V2(15.0, 6.0)),
-        Line(V2(15.0, 6.0), V2(23.0, 8.0)),
-        Line(V2(23.0, 8.0), V2(15.0, 3.0)),
-        Line(V2(15.0, 3.0), V2( 7.0, 8.0)),
+        Segment::Line(V2( 2.0, 9.0), V2(15.0, 1.0)),
+        Segment::Line(V2(15.0, 1.0), V2(28.0, 9.0)),
+        Segment::Line(V2(28.0, 9.0), V2( 2.0, 9.0)),
+
+        Segment::Line(V2( 7.0, 8.0), V2(15.0, 6.0)),
+        Segment::Line(V2(15.0, 6.0), V2(23.0, 8.0)),
+        Segment::Line(V2(23.0, 8.0), V2(15.0, 3.0)),
+        Segment::Line(V2(15.0, 3.0), V2( 7.0, 8.0)),
     ], 30, 10, FillRule::NonZero);
 
     let eight = vec![
-        Line(V2( 4.0,  2.0), V2(16.0,  2.0)),
-        Line(V2(16.0,  2.0), V2(16.0,  6.0)),
-        Line(V2(16.0,  6.0), V2( 6.0,  9.0)),
-        Line(V2( 6.0,  9.0), V2( 6.0, 13.0)),
-        Line(V2( 6.0, 13.0), V2(14.0, 13.0)),
-        Line(V2(14.0, 13.0), V2(14.0,  9.0)),
-        Line(V2(14.0,  9.0), V2( 4.0,  6.0)),
-        Line(V2( 4.0,  6.0), V2( 4.0,  2.0)),
-
-        Line(V2( 6.0,  6.0), V2(16.0,  9.0)),
-        Line(V2(16.0,  9.0), V2(16.0, 14.0)),
-        Line(V2(16.0, 14.0), V2( 4.0, 14.0)),
-        Line(V2( 4.0, 14.0), V2( 4.0,  9.0)),
-        Line(V2( 4.0,  9.0), V2(14.0,  6.0)),
-        Line(V2(14.0,  6.0), V2(14.0,  3.0)),
-        Line(V2(14.0,  3.0), V2( 6.0,  3.0)),
-        Line(V2( 6.0,  3.0), V2( 6.0,  6.0)),
+        Segment::Line(V2( 4.0,  2.0), V2(16.0,  2.0)),
+        Segment::Line(V2(16.0,  2.0), V2(16.0,  6.0)),
+        Segment::Line(V2(16.0,  6.0), V2( 6.0,  9.0)),
+        Segment::Line(V2( 6.0,  9.0), V2( 6.0, 13.0)),
+        Segment::Line(V2( 6.0, 13.0), V2(14.0, 13.0)),
+        Segment::Line(V2(14.0, 13.0), V2(14.0,  9.0)),
+        Segment::Line(V2(14.0,  9.0), V2( 4.0,  6.0)),
+        Segment::Line(V2( 4.0,  6.0), V2( 4.0,  2.0)),
+
+        Segment::Line(V2( 6.0,  6.0), V2(16.0,  9.0)),
+        Segment::Line(V2(16.0,  9.0), V2(16.0, 14.0)),
+        Segment::Line(V2(16.0, 14.0), V2( 4.0, 14.0)),
+        Segment::Line(V2( 4.0, 14.0), V2( 4.0,  9.0)),
+        Segment::Line(V2( 4.0,  9.0), V2(14.0,  6.0)),
+        Segment::Line(V2(14.0,  6.0), V2(14.0,  3.0)),
+        Segment::Line(V2(14.0,  3.0), V2( 6.0,  3.0)),
+        Segment::Line(V2( 6.0,  3.0), V2( 6.0,  6.0)),
     ];
     fill(&eight, 20, 16, FillRule::EvenOdd);
     fill(&eight, 20, 16, FillRule::NonZero);
 
     stroke(&vec![
-        Line(V2(3.5, 2.0), V2(3.5, 7.0)),
-        Line(V2(3.5, 4.5), V2(7.5, 4.5)),
-        Line(V2(7.5, 2.0), V2(7.5, 7.0)),
+        vec![Segment::Line(V2(3.5, 2.0), V2(3.5, 7.0))],
+        vec![Segment::Line(V2(3.5, 4.5), V2(7.5, 4.5))],
+        vec![Segment::Line(V2(7.5, 2.0), V2(7.5, 7.0))],
 
-        Line(V2(11.5, 2.0), V2(11.5, 7.0)),
+        vec![Segment::Line(V2(11.5, 2.0), V2(11.5, 7.0))],
     ], 15, 9, 1.0);
-}
 
+    // a single joined path, to exercise miter/bevel/round joins and caps.
+    let zigzag = vec![vec![
+        Segment::Line(V2(2.0, 7.0), V2(7.0, 2.0)),
+        Segment::Line(V2(7.0, 2.0), V2(12.0, 7.0)),
+        Segment::Line(V2(12.0, 7.0), V2(17.0, 2.0)),
+    ]];
+    stroke_styled(&zigzag, 20, 9, 1.5, JoinStyle::Round, CapStyle::Round, DEFAULT_MITER_LIMIT);
+    stroke_styled(&zigzag, 20, 9, 1.5, JoinStyle::Bevel, CapStyle::Square, DEFAULT_MITER_LIMIT);
+    // a tight miter limit on these sharp corners falls back to a bevel.
+    stroke_styled(&zigzag, 20, 9, 1.5, JoinStyle::Miter, CapStyle::Butt, 1.0);
+
+    // a rounded "D" made from two quadratic Béziers, to exercise curve flattening.
+    let rounded_d = vec![
+        Segment::Line(V2(4.0, 2.0), V2(4.0, 14.0)),
+        Segment::Quad(V2(4.0, 14.0), V2(16.0, 14.0), V2(16.0, 8.0)),
+        Segment::Quad(V2(16.0, 8.0), V2(16.0, 2.0), V2(4.0, 2.0)),
+    ];
+    fill(&rounded_d, 20, 16, FillRule::NonZero);
+
+    let coverage = fill_coverage(&rounded_d, 20, 16, FillRule::NonZero);
+    print_coverage(&coverage, 20, 16);
+
+    // a triangle that spills well outside the 10x10 canvas on every side,
+    // to exercise clip_to_rect.
+    fill(&vec![
+        Segment::Line(V2(-5.0, 12.0), V2( 5.0, -8.0)),
+        Segment::Line(V2( 5.0, -8.0), V2(15.0, 12.0)),
+        Segment::Line(V2(15.0, 12.0), V2(-5.0, 12.0)),
+    ], 10, 10, FillRule::NonZero);
+
+    // a sub-pixel-width diagonal line and a hairline cross, rendered via
+    // the signed distance field instead of a filled outline.
+    let thin_lines = vec![
+        vec![Segment::Line(V2(1.0, 1.0), V2(13.0, 9.0))],
+        vec![Segment::Line(V2(7.0, 1.0), V2(7.0, 9.0))],
+        vec![Segment::Line(V2(1.0, 5.0), V2(13.0, 5.0))],
+    ];
+    let coverage = stroke_sdf(&thin_lines, 14, 10, 0.6);
+    print_coverage(&coverage, 14, 10);
+
+    // a leaf shape from a single cubic Bézier, to exercise that variant.
+    fill(&vec![
+        Segment::Cubic(V2(8.0, 2.0), V2(1.0, 6.0), V2(1.0, 10.0), V2(8.0, 14.0)),
+        Segment::Line(V2(8.0, 14.0), V2(8.0, 2.0)),
+    ], 16, 16, FillRule::NonZero);
+}